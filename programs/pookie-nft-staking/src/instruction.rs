@@ -5,8 +5,8 @@ use solana_program::{
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum StakingInstruction {
-    /// Stake an NFT (Non-Transfer Model)
-    /// 
+    /// Stake an NFT (Non-Transfer Model), optionally time-locking it for a reward multiplier
+    ///
     /// Accounts expected:
     /// 0. `[signer, writable]` The owner of the NFT (payer for PDA creation)
     /// 1. `[]` The owner's NFT token account (checked for ownership)
@@ -16,7 +16,10 @@ pub enum StakingInstruction {
     /// 5. `[]` Rent sysvar
     /// 6. `[]` System program
     /// 7. `[]` Clock sysvar
-    StakeNft,
+    /// 8. `[]` Pool config account (PDA, "config"; supplies the lockup tier table)
+    StakeNft {
+        lock_days: u32,
+    },
 
     /// Unstake an NFT (Non-Transfer Model)
     /// 
@@ -26,6 +29,9 @@ pub enum StakingInstruction {
     /// 2. `[]` The NFT mint address
     /// 3. `[writable]` The stake account (PDA, closed)
     /// 4. `[]` SPL Token program
+    /// 5. `[]` Clock sysvar
+    ///
+    /// Fails with `StillLocked` if `Clock::unix_timestamp < StakeAccount::lock_until`.
     UnstakeNft,
 
     /// Claim rewards for a staked NFT (Non-Transfer Model)
@@ -41,30 +47,188 @@ pub enum StakingInstruction {
     /// 7. `[]` SPL Token program
     /// 8. `[]` Program Authority (PDA, "authority")
     /// 9. `[]` Clock sysvar
+    /// 10. `[]` Pool config account (PDA, "config")
     ClaimRewards,
+
+    /// Initialize the pool configuration account
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The pool admin (payer for PDA creation)
+    /// 1. `[writable]` The pool config account (PDA, seed "config")
+    /// 2. `[]` Reward token mint
+    /// 3. `[]` Treasury account holding reward tokens
+    /// 4. `[]` Rent sysvar
+    /// 5. `[]` System program
+    InitializePool {
+        reward_rate_per_day: u64,
+    },
+
+    /// Update the configured reward rate
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The pool admin (must match `PoolConfig::admin`)
+    /// 1. `[writable]` The pool config account (PDA, seed "config")
+    SetRewardRate {
+        reward_rate_per_day: u64,
+    },
+
+    /// Stake an NFT using the freeze model: the program authority is taken as
+    /// delegate and the token account is frozen so the NFT can't move while staked.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The owner of the NFT (payer for PDA creation)
+    /// 1. `[writable]` The owner's NFT token account (approved to and frozen by the program authority)
+    /// 2. `[]` The NFT mint address (freeze authority must be the program authority PDA)
+    /// 3. `[writable]` The stake account (PDA, created if needed)
+    /// 4. `[]` Program Authority (PDA, "authority")
+    /// 5. `[]` SPL Token program
+    /// 6. `[]` Rent sysvar
+    /// 7. `[]` System program
+    /// 8. `[]` Clock sysvar
+    StakeNftFrozen,
+
+    /// Unstake an NFT staked via the freeze model: thaws and revokes the
+    /// delegate before closing the stake account.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The owner of the NFT (receives lamports from closed PDA)
+    /// 1. `[writable]` The owner's NFT token account (thawed and revoked)
+    /// 2. `[]` The NFT mint address
+    /// 3. `[writable]` The stake account (PDA, closed)
+    /// 4. `[]` Program Authority (PDA, "authority")
+    /// 5. `[]` SPL Token program
+    UnstakeNftFrozen,
+
+    /// Claim rewards for several staked NFTs in a single instruction.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The owner of the NFTs
+    /// 1. `[writable]` Treasury account holding reward tokens
+    /// 2. `[]` Reward token mint
+    /// 3. `[]` SPL Token program
+    /// 4. `[]` Program Authority (PDA, "authority")
+    /// 5. `[]` Clock sysvar
+    /// 6. `[]` Pool config account (PDA, "config")
+    ///
+    /// Followed by `count` repetitions of:
+    /// - `[]` NFT token account (checked for ownership)
+    /// - `[]` NFT mint address
+    /// - `[writable]` Stake account (PDA, updated last_claim_time)
+    /// - `[writable]` User's reward token account
+    BatchClaimRewards {
+        count: u8,
+    },
+
+    /// Update the lockup tier table used to assign reward multipliers at stake time
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` The pool admin (must match `PoolConfig::admin`)
+    /// 1. `[writable]` The pool config account (PDA, seed "config")
+    SetLockupTiers {
+        tiers: [(u32, u16); LOCKUP_TIER_COUNT],
+    },
 }
 
+/// Number of (lock_days, multiplier_bps) entries in the lockup tier table, shared with `PoolConfig`
+pub const LOCKUP_TIER_COUNT: usize = 4;
+
 impl StakingInstruction {
     /// Unpacks a byte buffer into a StakingInstruction
     pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
-        let (tag, _rest) = input.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+        let (tag, rest) = input.split_first().ok_or(ProgramError::InvalidInstructionData)?;
 
         Ok(match tag {
-            0 => Self::StakeNft,
+            0 => Self::StakeNft {
+                lock_days: Self::unpack_u32(rest)?,
+            },
             1 => Self::UnstakeNft,
             2 => Self::ClaimRewards,
+            3 => Self::InitializePool {
+                reward_rate_per_day: Self::unpack_u64(rest)?,
+            },
+            4 => Self::SetRewardRate {
+                reward_rate_per_day: Self::unpack_u64(rest)?,
+            },
+            5 => Self::StakeNftFrozen,
+            6 => Self::UnstakeNftFrozen,
+            7 => Self::BatchClaimRewards {
+                count: *rest.first().ok_or(ProgramError::InvalidInstructionData)?,
+            },
+            8 => Self::SetLockupTiers {
+                tiers: Self::unpack_lockup_tiers(rest)?,
+            },
             _ => return Err(ProgramError::InvalidInstructionData),
         })
     }
 
+    /// Reads a little-endian u64 payload out of bytes 1..9 of the instruction data
+    fn unpack_u64(rest: &[u8]) -> Result<u64, ProgramError> {
+        let bytes = rest
+            .get(..8)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Reads a little-endian u32 payload out of bytes 1..5 of the instruction data
+    fn unpack_u32(rest: &[u8]) -> Result<u32, ProgramError> {
+        let bytes = rest
+            .get(..4)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    /// Reads `LOCKUP_TIER_COUNT` (lock_days: u32, multiplier_bps: u16) pairs, packed back to back
+    fn unpack_lockup_tiers(rest: &[u8]) -> Result<[(u32, u16); LOCKUP_TIER_COUNT], ProgramError> {
+        let mut tiers = [(0u32, 0u16); LOCKUP_TIER_COUNT];
+        for (i, tier) in tiers.iter_mut().enumerate() {
+            let offset = i * 6;
+            let days_bytes: [u8; 4] = rest
+                .get(offset..offset + 4)
+                .and_then(|slice| slice.try_into().ok())
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            let bps_bytes: [u8; 2] = rest
+                .get(offset + 4..offset + 6)
+                .and_then(|slice| slice.try_into().ok())
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            *tier = (u32::from_le_bytes(days_bytes), u16::from_le_bytes(bps_bytes));
+        }
+        Ok(tiers)
+    }
+
     /// Packs a StakingInstruction into a byte buffer
     pub fn pack(&self) -> Vec<u8> {
-        let mut buf = Vec::with_capacity(1);
+        let mut buf = Vec::with_capacity(9);
         match self {
-            Self::StakeNft => buf.push(0),
+            Self::StakeNft { lock_days } => {
+                buf.push(0);
+                buf.extend_from_slice(&lock_days.to_le_bytes());
+            }
             Self::UnstakeNft => buf.push(1),
             Self::ClaimRewards => buf.push(2),
+            Self::InitializePool { reward_rate_per_day } => {
+                buf.push(3);
+                buf.extend_from_slice(&reward_rate_per_day.to_le_bytes());
+            }
+            Self::SetRewardRate { reward_rate_per_day } => {
+                buf.push(4);
+                buf.extend_from_slice(&reward_rate_per_day.to_le_bytes());
+            }
+            Self::StakeNftFrozen => buf.push(5),
+            Self::UnstakeNftFrozen => buf.push(6),
+            Self::BatchClaimRewards { count } => {
+                buf.push(7);
+                buf.push(*count);
+            }
+            Self::SetLockupTiers { tiers } => {
+                buf.push(8);
+                for (days, bps) in tiers.iter() {
+                    buf.extend_from_slice(&days.to_le_bytes());
+                    buf.extend_from_slice(&bps.to_le_bytes());
+                }
+            }
         }
         buf
     }
-} 
\ No newline at end of file
+}
\ No newline at end of file
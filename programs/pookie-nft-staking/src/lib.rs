@@ -4,7 +4,7 @@ use solana_program::{
     entrypoint,
     entrypoint::ProgramResult,
     msg,
-    program::invoke_signed,
+    program::{invoke, invoke_signed},
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
     pubkey::Pubkey,
@@ -18,7 +18,7 @@ use arrayref::{array_ref, array_refs, array_mut_ref, mut_array_refs};
 
 // Import our instruction module
 mod instruction;
-pub use instruction::StakingInstruction;
+pub use instruction::{StakingInstruction, LOCKUP_TIER_COUNT};
 
 // Define errors
 #[derive(Error, Debug, Copy, Clone)]
@@ -61,6 +61,21 @@ pub enum StakingError {
 
     #[error("Insufficient funds")]
     InsufficientFunds,
+
+    #[error("Unauthorized")]
+    Unauthorized,
+
+    #[error("Unsupported token program")]
+    UnsupportedTokenProgram,
+
+    #[error("Same stake account supplied more than once in a batch")]
+    DuplicateStakeAccount,
+
+    #[error("Batch must include at least one NFT")]
+    EmptyBatch,
+
+    #[error("NFT is still within its lockup period")]
+    StillLocked,
 }
 
 impl From<StakingError> for ProgramError {
@@ -83,9 +98,23 @@ pub fn process_instruction(
 
     // Process the instruction
     match instruction {
-        StakingInstruction::StakeNft => stake_nft(program_id, accounts),
+        StakingInstruction::StakeNft { lock_days } => stake_nft(program_id, accounts, lock_days),
         StakingInstruction::UnstakeNft => unstake_nft(program_id, accounts),
         StakingInstruction::ClaimRewards => claim_rewards(program_id, accounts),
+        StakingInstruction::InitializePool { reward_rate_per_day } => {
+            initialize_pool(program_id, accounts, reward_rate_per_day)
+        }
+        StakingInstruction::SetRewardRate { reward_rate_per_day } => {
+            set_reward_rate(program_id, accounts, reward_rate_per_day)
+        }
+        StakingInstruction::StakeNftFrozen => stake_nft_frozen(program_id, accounts),
+        StakingInstruction::UnstakeNftFrozen => unstake_nft_frozen(program_id, accounts),
+        StakingInstruction::BatchClaimRewards { count } => {
+            batch_claim_rewards(program_id, accounts, count)
+        }
+        StakingInstruction::SetLockupTiers { tiers } => {
+            set_lockup_tiers(program_id, accounts, tiers)
+        }
     }
 }
 
@@ -97,6 +126,9 @@ pub struct StakeAccount {
     pub nft_mint: Pubkey,
     pub stake_time: i64,
     pub last_claim_time: i64,
+    pub is_frozen_model: bool,
+    pub lock_until: i64,
+    pub multiplier_bps: u16,
 }
 
 impl Sealed for StakeAccount {}
@@ -108,7 +140,7 @@ impl IsInitialized for StakeAccount {
 }
 
 impl Pack for StakeAccount {
-    const LEN: usize = 1 + 32 + 32 + 8 + 8; // is_initialized + owner + nft_mint + stake_time + last_claim_time
+    const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1 + 8 + 2; // is_initialized + owner + nft_mint + stake_time + last_claim_time + is_frozen_model + lock_until + multiplier_bps
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
         let src = array_ref![src, 0, StakeAccount::LEN];
@@ -118,13 +150,19 @@ impl Pack for StakeAccount {
             nft_mint_src,
             stake_time_src,
             last_claim_time_src,
-        ) = array_refs![src, 1, 32, 32, 8, 8];
-        
+            is_frozen_model_src,
+            lock_until_src,
+            multiplier_bps_src,
+        ) = array_refs![src, 1, 32, 32, 8, 8, 1, 8, 2];
+
         let is_initialized = is_initialized_src[0] != 0;
         let owner = Pubkey::new_from_array(*owner_src);
         let nft_mint = Pubkey::new_from_array(*nft_mint_src);
         let stake_time = i64::from_le_bytes(*stake_time_src);
         let last_claim_time = i64::from_le_bytes(*last_claim_time_src);
+        let is_frozen_model = is_frozen_model_src[0] != 0;
+        let lock_until = i64::from_le_bytes(*lock_until_src);
+        let multiplier_bps = u16::from_le_bytes(*multiplier_bps_src);
 
         if is_initialized {
             Ok(StakeAccount {
@@ -133,6 +171,9 @@ impl Pack for StakeAccount {
                 nft_mint,
                 stake_time,
                 last_claim_time,
+                is_frozen_model,
+                lock_until,
+                multiplier_bps,
             })
         } else {
             // Handle case where the account is not initialized, maybe return default or error
@@ -152,16 +193,141 @@ impl Pack for StakeAccount {
             nft_mint_dst,
             stake_time_dst,
             last_claim_time_dst,
-        ) = mut_array_refs![dst_array_ref, 1, 32, 32, 8, 8]; // Apply to dst_array_ref
+            is_frozen_model_dst,
+            lock_until_dst,
+            multiplier_bps_dst,
+        ) = mut_array_refs![dst_array_ref, 1, 32, 32, 8, 8, 1, 8, 2]; // Apply to dst_array_ref
 
         is_initialized_dst[0] = self.is_initialized as u8;
         owner_dst.copy_from_slice(self.owner.as_ref());
         nft_mint_dst.copy_from_slice(self.nft_mint.as_ref());
         *stake_time_dst = self.stake_time.to_le_bytes();
         *last_claim_time_dst = self.last_claim_time.to_le_bytes();
+        is_frozen_model_dst[0] = self.is_frozen_model as u8;
+        *lock_until_dst = self.lock_until.to_le_bytes();
+        *multiplier_bps_dst = self.multiplier_bps.to_le_bytes();
+    }
+}
+
+// Size in bytes of the packed lockup tier table: LOCKUP_TIER_COUNT * (lock_days: u32 + multiplier_bps: u16)
+const LOCKUP_TABLE_LEN: usize = LOCKUP_TIER_COUNT * 6;
+
+// Pool configuration data structure
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PoolConfig {
+    pub is_initialized: bool,
+    pub admin: Pubkey,
+    pub reward_token_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub reward_rate_per_day: u64,
+    pub authority_bump: u8,
+    /// (lock_days, multiplier_bps) tiers used by `stake_nft` to assign a reward multiplier
+    pub lockup_tiers: [(u32, u16); LOCKUP_TIER_COUNT],
+}
+
+impl Sealed for PoolConfig {}
+
+impl IsInitialized for PoolConfig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
     }
 }
 
+impl Pack for PoolConfig {
+    const LEN: usize = 1 + 32 + 32 + 32 + 8 + 1 + LOCKUP_TABLE_LEN; // is_initialized + admin + reward_token_mint + treasury + reward_rate_per_day + authority_bump + lockup_tiers
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, PoolConfig::LEN];
+        let (
+            is_initialized_src,
+            admin_src,
+            reward_token_mint_src,
+            treasury_src,
+            reward_rate_per_day_src,
+            authority_bump_src,
+            lockup_tiers_src,
+        ) = array_refs![src, 1, 32, 32, 32, 8, 1, LOCKUP_TABLE_LEN];
+
+        let is_initialized = is_initialized_src[0] != 0;
+        let admin = Pubkey::new_from_array(*admin_src);
+        let reward_token_mint = Pubkey::new_from_array(*reward_token_mint_src);
+        let treasury = Pubkey::new_from_array(*treasury_src);
+        let reward_rate_per_day = u64::from_le_bytes(*reward_rate_per_day_src);
+        let authority_bump = authority_bump_src[0];
+        let mut lockup_tiers = [(0u32, 0u16); LOCKUP_TIER_COUNT];
+        for (i, tier) in lockup_tiers.iter_mut().enumerate() {
+            let offset = i * 6;
+            let days = u32::from_le_bytes(*array_ref![lockup_tiers_src, offset, 4]);
+            let bps = u16::from_le_bytes(*array_ref![lockup_tiers_src, offset + 4, 2]);
+            *tier = (days, bps);
+        }
+
+        if is_initialized {
+            Ok(PoolConfig {
+                is_initialized,
+                admin,
+                reward_token_mint,
+                treasury,
+                reward_rate_per_day,
+                authority_bump,
+                lockup_tiers,
+            })
+        } else {
+            Ok(PoolConfig::default())
+        }
+    }
+
+    fn pack_into_slice(&self, dst_slice: &mut [u8]) {
+        let dst_array_ref = array_mut_ref![dst_slice, 0, PoolConfig::LEN];
+        let (
+            is_initialized_dst,
+            admin_dst,
+            reward_token_mint_dst,
+            treasury_dst,
+            reward_rate_per_day_dst,
+            authority_bump_dst,
+            lockup_tiers_dst,
+        ) = mut_array_refs![dst_array_ref, 1, 32, 32, 32, 8, 1, LOCKUP_TABLE_LEN];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        admin_dst.copy_from_slice(self.admin.as_ref());
+        reward_token_mint_dst.copy_from_slice(self.reward_token_mint.as_ref());
+        treasury_dst.copy_from_slice(self.treasury.as_ref());
+        *reward_rate_per_day_dst = self.reward_rate_per_day.to_le_bytes();
+        authority_bump_dst[0] = self.authority_bump;
+        for (i, (days, bps)) in self.lockup_tiers.iter().enumerate() {
+            let offset = i * 6;
+            lockup_tiers_dst[offset..offset + 4].copy_from_slice(&days.to_le_bytes());
+            lockup_tiers_dst[offset + 4..offset + 6].copy_from_slice(&bps.to_le_bytes());
+        }
+    }
+}
+
+// Default lockup tier table used when a pool is first initialized
+const DEFAULT_LOCKUP_TIERS: [(u32, u16); LOCKUP_TIER_COUNT] = [
+    (0, 10_000),
+    (30, 12_500),
+    (90, 15_000),
+    (180, 20_000),
+];
+
+// Resolves the reward multiplier (in basis points) for a given lock duration by
+// taking the richest tier whose `lock_days` threshold is met, defaulting to 1x (10000 bps)
+fn resolve_multiplier_bps(pool_config: &PoolConfig, lock_days: u32) -> u16 {
+    pool_config
+        .lockup_tiers
+        .iter()
+        .filter(|(tier_days, _)| *tier_days <= lock_days)
+        .map(|(_, bps)| *bps)
+        .max()
+        .unwrap_or(10_000)
+}
+
+// Helper function to find PDA for the pool config account
+fn find_pool_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], program_id)
+}
+
 // Helper function to find PDA for stake account
 fn find_stake_account_address(
     nft_mint: &Pubkey,
@@ -188,6 +354,20 @@ fn find_program_authority(
     )
 }
 
+// The base (non-extension) account layout is identical between the legacy SPL Token
+// program and Token-2022; Token-2022 TLV extension data, if any, is appended after it.
+const TOKEN_ACCOUNT_BASE_LEN: usize = 165;
+
+// Returns Ok(()) if `token_program` is either the legacy SPL Token program or Token-2022
+fn check_supported_token_program(token_program: &AccountInfo) -> Result<(), ProgramError> {
+    if token_program.key == &spl_token::id() || token_program.key == &spl_token_2022::id() {
+        Ok(())
+    } else {
+        msg!("Unsupported token program");
+        Err(StakingError::UnsupportedTokenProgram.into())
+    }
+}
+
 // Validate token account
 fn validate_token_account(
     token_account: &AccountInfo,
@@ -196,19 +376,26 @@ fn validate_token_account(
     token_program: &AccountInfo,
     check_balance: bool, // Add flag to optionally check balance
 ) -> Result<(), ProgramError> {
+    check_supported_token_program(token_program)?;
+
     if token_account.owner != token_program.key {
         msg!("Token account not owned by token program");
         return Err(StakingError::InvalidTokenAccount.into());
     }
 
     let token_account_data = token_account.try_borrow_data()?;
-    
+    if token_account_data.len() < TOKEN_ACCOUNT_BASE_LEN {
+        msg!("Token account data shorter than the base account layout");
+        return Err(StakingError::InvalidTokenAccount.into());
+    }
+
+    // Parse only the base layout; any Token-2022 extension TLV data after it is ignored here
     let account_mint = Pubkey::new_from_array(*array_ref![token_account_data, 0, 32]);
     if account_mint != *expected_mint {
         msg!("Token account mint does not match expected mint");
         return Err(StakingError::InvalidMint.into());
     }
-    
+
     let account_owner = Pubkey::new_from_array(*array_ref![token_account_data, 32, 32]);
     if account_owner != *expected_owner {
         msg!("Token account owner does not match expected owner");
@@ -221,17 +408,33 @@ fn validate_token_account(
         if amount != 1 {
             msg!("NFT Token account does not hold exactly one token");
             // Consider a more specific error, reusing InvalidTokenAccount for now
-            return Err(StakingError::InvalidTokenAccount.into()); 
+            return Err(StakingError::InvalidTokenAccount.into());
         }
     }
 
     Ok(())
 }
 
+// The base (non-extension) SPL Token `Mint` layout is identical between the legacy
+// SPL Token program and Token-2022; Token-2022 TLV extension data, if any, is appended
+// after it, so reading `decimals` at its fixed offset keeps mints with extensions
+// readable instead of going through `Pack::unpack`, which rejects non-Mint::LEN data.
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+fn read_mint_decimals(mint: &AccountInfo) -> Result<u8, ProgramError> {
+    let mint_data = mint.try_borrow_data()?;
+    if mint_data.len() <= MINT_DECIMALS_OFFSET {
+        msg!("Mint account data shorter than the base mint layout");
+        return Err(StakingError::InvalidMint.into());
+    }
+    Ok(mint_data[MINT_DECIMALS_OFFSET])
+}
+
 // Stake an NFT
 fn stake_nft(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    lock_days: u32,
 ) -> ProgramResult {
     msg!("Instruction: Stake NFT (Non-Transfer)");
     let accounts_iter = &mut accounts.iter();
@@ -245,6 +448,7 @@ fn stake_nft(
     let rent_info = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
     let clock_info = next_account_info(accounts_iter)?;
+    let pool_config_account = next_account_info(accounts_iter)?;
 
     if !user.is_signer {
         msg!("User must be signer");
@@ -319,6 +523,26 @@ fn stake_nft(
     let clock = Clock::from_account_info(clock_info)?;
     let current_time = clock.unix_timestamp;
 
+    // Verify pool config PDA matches and read the lockup tier table
+    let (pool_config_pda, _config_bump) = find_pool_config_address(program_id);
+    if pool_config_pda != *pool_config_account.key {
+        msg!("Invalid pool config PDA provided");
+        return Err(StakingError::InvalidPDA.into());
+    }
+    let pool_config = PoolConfig::unpack(&pool_config_account.data.borrow())?;
+    if !pool_config.is_initialized {
+        msg!("Pool config is not initialized");
+        return Err(StakingError::NotInitialized.into());
+    }
+
+    let multiplier_bps = resolve_multiplier_bps(&pool_config, lock_days);
+    let lock_seconds = i64::from(lock_days)
+        .checked_mul(86400)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+    let lock_until = current_time
+        .checked_add(lock_seconds)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
     // Initialize stake account data
     msg!("Initializing stake account data");
     // No need to unpack_unchecked again, just create the data directly
@@ -328,6 +552,9 @@ fn stake_nft(
         nft_mint: *nft_mint.key,
         stake_time: current_time,
         last_claim_time: current_time,
+        is_frozen_model: false,
+        lock_until,
+        multiplier_bps,
     };
     StakeAccount::pack(stake_data, &mut stake_account.data.borrow_mut())?;
     msg!("Stake account data initialized successfully");
@@ -350,6 +577,7 @@ fn unstake_nft(
     let nft_mint = next_account_info(accounts_iter)?;
     let stake_account = next_account_info(accounts_iter)?;
     let token_program = next_account_info(accounts_iter)?;
+    let clock_info = next_account_info(accounts_iter)?;
 
     if !user.is_signer {
         msg!("User must be signer");
@@ -382,6 +610,16 @@ fn unstake_nft(
         msg!("Stake account data does not match user or NFT mint");
         return Err(StakingError::InvalidOwner.into());
     }
+    if stake_data.is_frozen_model {
+        msg!("Stake account was staked via the freeze model; use UnstakeNftFrozen instead");
+        return Err(StakingError::InvalidOwner.into());
+    }
+
+    let clock = Clock::from_account_info(clock_info)?;
+    if clock.unix_timestamp < stake_data.lock_until {
+        msg!("NFT is still within its lockup period");
+        return Err(StakingError::StillLocked.into());
+    }
 
     // REMOVED program authority PDA derivation
     // REMOVED program's NFT token account validation
@@ -426,6 +664,7 @@ fn claim_rewards(
     let token_program = next_account_info(accounts_iter)?;
     let program_authority = next_account_info(accounts_iter)?;
     let clock_info = next_account_info(accounts_iter)?;
+    let pool_config_account = next_account_info(accounts_iter)?;
 
     if !user.is_signer {
         msg!("User must be signer");
@@ -472,14 +711,33 @@ fn claim_rewards(
     // Verify the treasury account belongs to the authority and matches the reward token mint
     validate_token_account(treasury_account, &authority_pda, reward_token_mint.key, token_program, false)?;
 
+    // Verify pool config PDA matches and read the configured reward rate
+    let (pool_config_pda, _config_bump) = find_pool_config_address(program_id);
+    if pool_config_pda != *pool_config_account.key {
+        msg!("Invalid pool config PDA provided");
+        return Err(StakingError::InvalidPDA.into());
+    }
+    let pool_config = PoolConfig::unpack(&pool_config_account.data.borrow())?;
+    if !pool_config.is_initialized {
+        msg!("Pool config is not initialized");
+        return Err(StakingError::NotInitialized.into());
+    }
+    if pool_config.reward_token_mint != *reward_token_mint.key {
+        msg!("Reward token mint does not match the configured pool mint");
+        return Err(StakingError::InvalidMint.into());
+    }
+    if pool_config.treasury != *treasury_account.key {
+        msg!("Treasury account does not match the configured pool treasury");
+        return Err(StakingError::InvalidTokenAccount.into());
+    }
+
     // Calculate rewards
     let clock = Clock::from_account_info(clock_info)?;
     let current_time = clock.unix_timestamp;
     let last_claim_time = stake_data.last_claim_time;
 
-    // TODO: Make reward rate configurable (e.g., read from another account)
     const SECONDS_PER_DAY: i64 = 86400; // 24 * 60 * 60
-    const REWARD_RATE_PER_DAY: u64 = 250 * 10u64.pow(9); // 250 tokens per day (assuming 9 decimals)
+    let reward_rate_per_day = pool_config.reward_rate_per_day;
 
     if current_time <= last_claim_time {
         msg!("No time elapsed since last claim, no rewards to claim.");
@@ -489,14 +747,18 @@ fn claim_rewards(
     let time_staked = current_time.checked_sub(last_claim_time)
         .ok_or(StakingError::ArithmeticOverflow)?; // Should not happen
 
-    // Calculate reward amount based on time staked
+    // Calculate reward amount based on time staked, boosted by the lockup multiplier
     // Using u128 for intermediate calculation to prevent overflow
     let reward_amount_u128 = (time_staked as u128)
-        .checked_mul(REWARD_RATE_PER_DAY as u128)
+        .checked_mul(reward_rate_per_day as u128)
         .ok_or(StakingError::ArithmeticOverflow)?
         .checked_div(SECONDS_PER_DAY as u128)
-        .ok_or(StakingError::ArithmeticOverflow)?; // Avoid division by zero, though SECONDS_PER_DAY is constant
-        
+        .ok_or(StakingError::ArithmeticOverflow)? // Avoid division by zero, though SECONDS_PER_DAY is constant
+        .checked_mul(stake_data.multiplier_bps as u128)
+        .ok_or(StakingError::ArithmeticOverflow)?
+        .checked_div(10_000)
+        .ok_or(StakingError::ArithmeticOverflow)?;
+
     let reward_amount: u64 = reward_amount_u128
         .try_into() // Remove explicit type <u64>
         .map_err(|_| StakingError::ArithmeticOverflow)?; // Convert back to u64
@@ -516,20 +778,28 @@ fn claim_rewards(
         msg!("Treasury balance insufficient to pay rewards");
         return Err(StakingError::InsufficientFunds.into());
     }
+    drop(treasury_data);
+
+    // transfer_checked requires the reward mint's decimals, which also guards against
+    // a decimals mismatch slipping a wrong-mint transfer through
+    let reward_mint_decimals = read_mint_decimals(reward_token_mint)?;
 
     // Transfer rewards from treasury to user
     msg!("Transferring {} reward tokens from treasury to user", reward_amount);
     invoke_signed(
-        &token_instruction::transfer(
+        &token_instruction::transfer_checked(
             token_program.key,
             treasury_account.key,
+            reward_token_mint.key,
             user_reward_account.key,
             &authority_pda,
             &[],
             reward_amount,
+            reward_mint_decimals,
         )?,
         &[
             treasury_account.clone(),
+            reward_token_mint.clone(),
             user_reward_account.clone(),
             program_authority.clone(), // Authority needs to be signer
             token_program.clone(),
@@ -544,4 +814,586 @@ fn claim_rewards(
 
     msg!("Rewards Claimed Successfully!");
     Ok(())
-} 
\ No newline at end of file
+}
+
+// Initialize the pool configuration account
+fn initialize_pool(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reward_rate_per_day: u64,
+) -> ProgramResult {
+    msg!("Instruction: Initialize Pool");
+    let accounts_iter = &mut accounts.iter();
+
+    let admin = next_account_info(accounts_iter)?;
+    let pool_config_account = next_account_info(accounts_iter)?;
+    let reward_token_mint = next_account_info(accounts_iter)?;
+    let treasury_account = next_account_info(accounts_iter)?;
+    let rent_info = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !admin.is_signer {
+        msg!("Admin must be signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (pool_config_pda, config_bump) = find_pool_config_address(program_id);
+    if pool_config_pda != *pool_config_account.key {
+        msg!("Pool config address does not match the derived PDA");
+        return Err(StakingError::InvalidPDA.into());
+    }
+
+    let (_authority_pda, authority_bump) = find_program_authority(program_id);
+
+    if pool_config_account.data_is_empty() {
+        msg!("Creating new pool config PDA");
+        let rent = &Rent::from_account_info(rent_info)?;
+        let space = PoolConfig::LEN;
+        let lamports = rent.minimum_balance(space);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                admin.key,
+                pool_config_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            ),
+            &[
+                admin.clone(),
+                pool_config_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"config", &[config_bump]]],
+        )?;
+        msg!("Pool config PDA created");
+    } else {
+        let config_data = PoolConfig::unpack_unchecked(&pool_config_account.data.borrow())?;
+        if config_data.is_initialized {
+            msg!("Pool config is already initialized");
+            return Err(StakingError::AlreadyInitialized.into());
+        }
+        msg!("Pool config PDA exists but is uninitialized. Proceeding.");
+    }
+
+    let config_data = PoolConfig {
+        is_initialized: true,
+        admin: *admin.key,
+        reward_token_mint: *reward_token_mint.key,
+        treasury: *treasury_account.key,
+        reward_rate_per_day,
+        authority_bump,
+        lockup_tiers: DEFAULT_LOCKUP_TIERS,
+    };
+    PoolConfig::pack(config_data, &mut pool_config_account.data.borrow_mut())?;
+
+    msg!("Pool Initialized Successfully!");
+    Ok(())
+}
+
+// Update the configured reward rate
+fn set_reward_rate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reward_rate_per_day: u64,
+) -> ProgramResult {
+    msg!("Instruction: Set Reward Rate");
+    let accounts_iter = &mut accounts.iter();
+
+    let admin = next_account_info(accounts_iter)?;
+    let pool_config_account = next_account_info(accounts_iter)?;
+
+    if !admin.is_signer {
+        msg!("Admin must be signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (pool_config_pda, _config_bump) = find_pool_config_address(program_id);
+    if pool_config_pda != *pool_config_account.key {
+        msg!("Pool config address does not match the derived PDA");
+        return Err(StakingError::InvalidPDA.into());
+    }
+
+    let mut config_data = PoolConfig::unpack(&pool_config_account.data.borrow())?;
+    if !config_data.is_initialized {
+        msg!("Pool config is not initialized");
+        return Err(StakingError::NotInitialized.into());
+    }
+    if config_data.admin != *admin.key {
+        msg!("Only the pool admin may update the reward rate");
+        return Err(StakingError::Unauthorized.into());
+    }
+
+    config_data.reward_rate_per_day = reward_rate_per_day;
+    PoolConfig::pack(config_data, &mut pool_config_account.data.borrow_mut())?;
+
+    msg!("Reward Rate Updated Successfully!");
+    Ok(())
+}
+
+// Update the lockup tier table used to assign reward multipliers at stake time
+fn set_lockup_tiers(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    tiers: [(u32, u16); LOCKUP_TIER_COUNT],
+) -> ProgramResult {
+    msg!("Instruction: Set Lockup Tiers");
+    let accounts_iter = &mut accounts.iter();
+
+    let admin = next_account_info(accounts_iter)?;
+    let pool_config_account = next_account_info(accounts_iter)?;
+
+    if !admin.is_signer {
+        msg!("Admin must be signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (pool_config_pda, _config_bump) = find_pool_config_address(program_id);
+    if pool_config_pda != *pool_config_account.key {
+        msg!("Pool config address does not match the derived PDA");
+        return Err(StakingError::InvalidPDA.into());
+    }
+
+    let mut config_data = PoolConfig::unpack(&pool_config_account.data.borrow())?;
+    if !config_data.is_initialized {
+        msg!("Pool config is not initialized");
+        return Err(StakingError::NotInitialized.into());
+    }
+    if config_data.admin != *admin.key {
+        msg!("Only the pool admin may update the lockup tiers");
+        return Err(StakingError::Unauthorized.into());
+    }
+
+    config_data.lockup_tiers = tiers;
+    PoolConfig::pack(config_data, &mut pool_config_account.data.borrow_mut())?;
+
+    msg!("Lockup Tiers Updated Successfully!");
+    Ok(())
+}
+
+// Stake an NFT using the freeze model: delegate to the program authority and freeze the token account
+fn stake_nft_frozen(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Instruction: Stake NFT (Freeze Model)");
+    let accounts_iter = &mut accounts.iter();
+
+    let user = next_account_info(accounts_iter)?;
+    let nft_token_account = next_account_info(accounts_iter)?;
+    let nft_mint = next_account_info(accounts_iter)?;
+    let stake_account = next_account_info(accounts_iter)?;
+    let program_authority = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let rent_info = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let clock_info = next_account_info(accounts_iter)?;
+
+    if !user.is_signer {
+        msg!("User must be signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    validate_token_account(nft_token_account, user.key, nft_mint.key, token_program, true)?;
+
+    let (authority_pda, authority_bump) = find_program_authority(program_id);
+    if authority_pda != *program_authority.key {
+        msg!("Invalid program authority PDA provided");
+        return Err(StakingError::InvalidPDA.into());
+    }
+
+    let (stake_account_pda, bump_seed) = find_stake_account_address(
+        nft_mint.key,
+        user.key,
+        program_id,
+    );
+
+    if stake_account_pda != *stake_account.key {
+        msg!("Stake account address does not match the derived PDA");
+        return Err(StakingError::InvalidPDA.into());
+    }
+
+    if stake_account.data_is_empty() {
+        msg!("Creating new stake account PDA");
+        let rent = &Rent::from_account_info(rent_info)?;
+        let space = StakeAccount::LEN;
+        let lamports = rent.minimum_balance(space);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                user.key,
+                stake_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            ),
+            &[
+                user.clone(),
+                stake_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[
+                b"stake",
+                nft_mint.key.as_ref(),
+                user.key.as_ref(),
+                &[bump_seed],
+            ]],
+        )?;
+        msg!("Stake account PDA created");
+    } else {
+        let stake_data = StakeAccount::unpack_unchecked(&stake_account.data.borrow())?;
+        if stake_data.is_initialized {
+            msg!("Stake account is already initialized for this NFT");
+            return Err(StakingError::AlreadyInitialized.into());
+        }
+        msg!("Stake account PDA exists but is uninitialized. Proceeding.");
+    }
+
+    // Delegate the single token to the program authority while it is staked
+    // (freezing/thawing is done by the program authority as the mint's freeze authority)
+    msg!("Approving program authority as delegate");
+    invoke(
+        &token_instruction::approve(
+            token_program.key,
+            nft_token_account.key,
+            program_authority.key,
+            user.key,
+            &[],
+            1,
+        )?,
+        &[
+            nft_token_account.clone(),
+            program_authority.clone(),
+            user.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    // Freeze the token account so the NFT can't be transferred while staked
+    msg!("Freezing NFT token account");
+    invoke_signed(
+        &token_instruction::freeze_account(
+            token_program.key,
+            nft_token_account.key,
+            nft_mint.key,
+            program_authority.key,
+            &[],
+        )?,
+        &[
+            nft_token_account.clone(),
+            nft_mint.clone(),
+            program_authority.clone(),
+            token_program.clone(),
+        ],
+        &[&[b"authority", &[authority_bump]]],
+    )?;
+
+    let clock = Clock::from_account_info(clock_info)?;
+    let current_time = clock.unix_timestamp;
+
+    msg!("Initializing stake account data");
+    let stake_data = StakeAccount {
+        is_initialized: true,
+        owner: *user.key,
+        nft_mint: *nft_mint.key,
+        stake_time: current_time,
+        last_claim_time: current_time,
+        is_frozen_model: true,
+        lock_until: 0,
+        multiplier_bps: 10_000,
+    };
+    StakeAccount::pack(stake_data, &mut stake_account.data.borrow_mut())?;
+
+    msg!("NFT Staked (Freeze Model) Successfully!");
+    Ok(())
+}
+
+// Unstake an NFT staked via the freeze model: thaw and revoke before closing the stake PDA
+fn unstake_nft_frozen(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Instruction: Unstake NFT (Freeze Model)");
+    let accounts_iter = &mut accounts.iter();
+
+    let user = next_account_info(accounts_iter)?;
+    let nft_token_account = next_account_info(accounts_iter)?;
+    let nft_mint = next_account_info(accounts_iter)?;
+    let stake_account = next_account_info(accounts_iter)?;
+    let program_authority = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+
+    if !user.is_signer {
+        msg!("User must be signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    validate_token_account(nft_token_account, user.key, nft_mint.key, token_program, true)?;
+
+    let (authority_pda, authority_bump) = find_program_authority(program_id);
+    if authority_pda != *program_authority.key {
+        msg!("Invalid program authority PDA provided");
+        return Err(StakingError::InvalidPDA.into());
+    }
+
+    let (stake_account_pda, _bump_seed) = find_stake_account_address(
+        nft_mint.key,
+        user.key,
+        program_id,
+    );
+    if stake_account_pda != *stake_account.key {
+        msg!("Stake account address does not match the derived PDA");
+        return Err(StakingError::InvalidPDA.into());
+    }
+
+    let stake_data = StakeAccount::unpack(&stake_account.data.borrow())?;
+    if !stake_data.is_initialized {
+        msg!("Stake account is not initialized");
+        return Err(StakingError::NotInitialized.into());
+    }
+    if stake_data.owner != *user.key || stake_data.nft_mint != *nft_mint.key {
+        msg!("Stake account data does not match user or NFT mint");
+        return Err(StakingError::InvalidOwner.into());
+    }
+    if !stake_data.is_frozen_model {
+        msg!("Stake account was not staked via the freeze model");
+        return Err(StakingError::InvalidOwner.into());
+    }
+
+    // Thaw the token account before it can be moved again
+    msg!("Thawing NFT token account");
+    invoke_signed(
+        &token_instruction::thaw_account(
+            token_program.key,
+            nft_token_account.key,
+            nft_mint.key,
+            program_authority.key,
+            &[],
+        )?,
+        &[
+            nft_token_account.clone(),
+            nft_mint.clone(),
+            program_authority.clone(),
+            token_program.clone(),
+        ],
+        &[&[b"authority", &[authority_bump]]],
+    )?;
+
+    // Revoke the program authority's delegation
+    msg!("Revoking delegate");
+    invoke(
+        &token_instruction::revoke(
+            token_program.key,
+            nft_token_account.key,
+            user.key,
+            &[],
+        )?,
+        &[
+            nft_token_account.clone(),
+            user.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    msg!("Closing stake account and returning lamports");
+    let stake_lamports = stake_account.lamports();
+    **stake_account.try_borrow_mut_lamports()? = 0;
+
+    let mut user_lamports = user.try_borrow_mut_lamports()?;
+    **user_lamports = user_lamports
+        .checked_add(stake_lamports)
+        .ok_or(StakingError::LamportTransferOverflow)?;
+
+    let mut stake_data_mut = stake_account.data.borrow_mut();
+    stake_data_mut.fill(0);
+    msg!("Stake account zeroed");
+
+    msg!("NFT Unstaked (Freeze Model) Successfully!");
+    Ok(())
+}
+
+// Claim rewards for several staked NFTs in one instruction
+fn batch_claim_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    count: u8,
+) -> ProgramResult {
+    msg!("Instruction: Batch Claim Rewards ({} NFTs)", count);
+    if count == 0 {
+        msg!("Batch must include at least one NFT");
+        return Err(StakingError::EmptyBatch.into());
+    }
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Shared accounts, read once
+    let user = next_account_info(accounts_iter)?;
+    let treasury_account = next_account_info(accounts_iter)?;
+    let reward_token_mint = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let program_authority = next_account_info(accounts_iter)?;
+    let clock_info = next_account_info(accounts_iter)?;
+    let pool_config_account = next_account_info(accounts_iter)?;
+
+    if !user.is_signer {
+        msg!("User must be signer");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (authority_pda, authority_bump) = find_program_authority(program_id);
+    if authority_pda != *program_authority.key {
+        msg!("Invalid program authority PDA provided");
+        return Err(StakingError::InvalidPDA.into());
+    }
+
+    validate_token_account(treasury_account, &authority_pda, reward_token_mint.key, token_program, false)?;
+
+    let (pool_config_pda, _config_bump) = find_pool_config_address(program_id);
+    if pool_config_pda != *pool_config_account.key {
+        msg!("Invalid pool config PDA provided");
+        return Err(StakingError::InvalidPDA.into());
+    }
+    let pool_config = PoolConfig::unpack(&pool_config_account.data.borrow())?;
+    if !pool_config.is_initialized {
+        msg!("Pool config is not initialized");
+        return Err(StakingError::NotInitialized.into());
+    }
+    if pool_config.reward_token_mint != *reward_token_mint.key {
+        msg!("Reward token mint does not match the configured pool mint");
+        return Err(StakingError::InvalidMint.into());
+    }
+    if pool_config.treasury != *treasury_account.key {
+        msg!("Treasury account does not match the configured pool treasury");
+        return Err(StakingError::InvalidTokenAccount.into());
+    }
+
+    let clock = Clock::from_account_info(clock_info)?;
+    let current_time = clock.unix_timestamp;
+
+    const SECONDS_PER_DAY: i64 = 86400;
+    let reward_rate_per_day = pool_config.reward_rate_per_day;
+
+    // The same stake account may not legitimately appear twice within a batch (that
+    // would double-count rewards), so track the ones already processed; a repeated
+    // NFT mint derives the same stake PDA and is rejected the same way. The runtime
+    // does allow the same reward account to be passed more than once across entries,
+    // and every entry must name the same one, since the aggregate is paid into it;
+    // its data is only borrowed briefly for validation within each iteration.
+    let mut seen_stake_accounts: Vec<Pubkey> = Vec::with_capacity(count as usize);
+    let mut total_reward_amount: u64 = 0;
+    let mut payout_account_info: Option<AccountInfo> = None;
+
+    for _ in 0..count {
+        let nft_token_account = next_account_info(accounts_iter)?;
+        let nft_mint = next_account_info(accounts_iter)?;
+        let stake_account = next_account_info(accounts_iter)?;
+        let user_reward_account = next_account_info(accounts_iter)?;
+
+        let (stake_account_pda, _bump_seed) = find_stake_account_address(
+            nft_mint.key,
+            user.key,
+            program_id,
+        );
+        if stake_account_pda != *stake_account.key {
+            msg!("Stake account address does not match the derived PDA");
+            return Err(StakingError::InvalidPDA.into());
+        }
+        if seen_stake_accounts.contains(stake_account.key) {
+            msg!("Stake account supplied more than once in batch");
+            return Err(StakingError::DuplicateStakeAccount.into());
+        }
+        seen_stake_accounts.push(*stake_account.key);
+
+        let mut stake_data = StakeAccount::unpack(&stake_account.data.borrow())?;
+        if !stake_data.is_initialized {
+            msg!("Stake account is not initialized");
+            return Err(StakingError::NotInitialized.into());
+        }
+        if stake_data.owner != *user.key || stake_data.nft_mint != *nft_mint.key {
+            msg!("Stake account data does not match user or NFT mint");
+            return Err(StakingError::InvalidOwner.into());
+        }
+
+        validate_token_account(nft_token_account, user.key, nft_mint.key, token_program, true)?;
+        validate_token_account(user_reward_account, user.key, reward_token_mint.key, token_program, false)?;
+
+        match &payout_account_info {
+            None => payout_account_info = Some(user_reward_account.clone()),
+            Some(expected) if expected.key == user_reward_account.key => {}
+            Some(_) => {
+                msg!("All NFTs in a batch must pay into the same reward account");
+                return Err(StakingError::InvalidTokenAccountOwner.into());
+            }
+        }
+
+        let last_claim_time = stake_data.last_claim_time;
+        if current_time > last_claim_time {
+            let time_staked = current_time.checked_sub(last_claim_time)
+                .ok_or(StakingError::ArithmeticOverflow)?;
+
+            let reward_amount_u128 = (time_staked as u128)
+                .checked_mul(reward_rate_per_day as u128)
+                .ok_or(StakingError::ArithmeticOverflow)?
+                .checked_div(SECONDS_PER_DAY as u128)
+                .ok_or(StakingError::ArithmeticOverflow)?
+                .checked_mul(stake_data.multiplier_bps as u128)
+                .ok_or(StakingError::ArithmeticOverflow)?
+                .checked_div(10_000)
+                .ok_or(StakingError::ArithmeticOverflow)?;
+
+            let reward_amount: u64 = reward_amount_u128
+                .try_into()
+                .map_err(|_| StakingError::ArithmeticOverflow)?;
+
+            if reward_amount > 0 {
+                total_reward_amount = total_reward_amount
+                    .checked_add(reward_amount)
+                    .ok_or(StakingError::ArithmeticOverflow)?;
+
+                stake_data.last_claim_time = current_time;
+                StakeAccount::pack(stake_data, &mut stake_account.data.borrow_mut())?;
+            }
+        }
+    }
+
+    if total_reward_amount == 0 {
+        msg!("No rewards accrued across the batch");
+        return Ok(());
+    }
+
+    let treasury_data = treasury_account.try_borrow_data()?;
+    let treasury_balance = u64::from_le_bytes(*array_ref![treasury_data, 64, 8]);
+    if treasury_balance < total_reward_amount {
+        msg!("Treasury balance insufficient to pay rewards");
+        return Err(StakingError::InsufficientFunds.into());
+    }
+    drop(treasury_data);
+
+    let reward_mint_decimals = read_mint_decimals(reward_token_mint)?;
+    let payout_account_info = payout_account_info.expect("batch is non-empty, checked above");
+
+    msg!("Transferring {} reward tokens from treasury to user (aggregate of {} NFTs)", total_reward_amount, count);
+    invoke_signed(
+        &token_instruction::transfer_checked(
+            token_program.key,
+            treasury_account.key,
+            reward_token_mint.key,
+            payout_account_info.key,
+            &authority_pda,
+            &[],
+            total_reward_amount,
+            reward_mint_decimals,
+        )?,
+        &[
+            treasury_account.clone(),
+            reward_token_mint.clone(),
+            payout_account_info.clone(),
+            program_authority.clone(),
+            token_program.clone(),
+        ],
+        &[&[b"authority", &[authority_bump]]],
+    )?;
+
+    msg!("Batch Rewards Claimed Successfully!");
+    Ok(())
+}